@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
 
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
 use kube::{CustomResource, ResourceExt};
 use kubizone_crds::v1alpha1::ZoneRef;
 use schemars::JsonSchema;
@@ -12,9 +13,14 @@ use serde::{Deserialize, Serialize};
 /// zones change.
 pub const TARGET_ZONEFILE_LABEL: &str = "kubi.zone/zonefile";
 
-/// A [`ZoneFile`] references an upstream [`Zone`](kubizone_crds::Zone) and (re)builds
-/// a configmap of the same name, whenever the zone changes, automatically incrementing
-/// serials as necessary.
+/// Key written into `status.hash`, `status.serial`, `status.output`, and `status.history`
+/// in place of a per-zone name when `spec.renderMode` is [`RenderMode::Combined`], since
+/// all referenced zones share a single unified output under that mode.
+pub const COMBINED_OUTPUT_KEY: &str = "combined";
+
+/// A [`ZoneFile`] references an upstream [`Zone`](kubizone_crds::Zone) and (re)publishes
+/// it to the backend configured in `spec.output` (a `ConfigMap` of the same name, by
+/// default) whenever the zone changes, automatically incrementing serials as necessary.
 #[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema, Hash)]
 #[kube(
     group = "kubi.zone",
@@ -33,6 +39,260 @@ pub struct ZoneFileSpec {
 
     #[serde(default)]
     pub config_map_name: Option<String>,
+
+    /// Tunable parameters for the rendered zone's SOA record.
+    ///
+    /// When omitted, RFC-aligned defaults are used for every field.
+    #[serde(default)]
+    pub soa: Option<SoaSpec>,
+
+    /// Strategy used to compute the next zone serial whenever the zonefile is rebuilt.
+    #[serde(default)]
+    pub serial_policy: SerialPolicy,
+
+    /// Backend the rendered zonefile is published to.
+    ///
+    /// Defaults to (re)building a `ConfigMap` of the same name as the `ZoneFile`,
+    /// preserving the original behavior.
+    #[serde(default)]
+    pub output: OutputSpec,
+
+    /// How the zones referenced by `zoneRefs` are rendered into the output.
+    #[serde(default)]
+    pub render_mode: RenderMode,
+
+    /// Number of historical entries retained per zone in `status.history`.
+    ///
+    /// Oldest entries are trimmed once this depth is exceeded.
+    #[serde(default = "default_history_depth")]
+    pub history_depth: u32,
+}
+
+fn default_history_depth() -> u32 {
+    10
+}
+
+/// How the zones referenced by a [`ZoneFile`]'s `zoneRefs` are rendered into output.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum RenderMode {
+    /// Render each referenced zone to its own output entry, keyed by zone name.
+    #[default]
+    Separate,
+
+    /// Concatenate all referenced zones into a single rendered zonefile, sharing
+    /// a single unified serial.
+    ///
+    /// The per-zone `status.hash`/`.serial`/`.output`/`.history` maps are written
+    /// with the single key [`COMBINED_OUTPUT_KEY`] instead of one entry per zone.
+    ///
+    /// Duplicate apex records or overlapping `$ORIGIN`s across the referenced
+    /// zones are surfaced as a status condition rather than silently rendered.
+    Combined,
+}
+
+/// Reference to a key within a `Secret`, in the same namespace as the `ZoneFile`.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretRef {
+    /// Name of the `Secret`.
+    pub name: String,
+
+    /// Key within the secret's data map.
+    pub key: String,
+}
+
+/// Destination a rendered zonefile is published to.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema, Hash)]
+#[serde(tag = "backend", rename_all = "camelCase")]
+pub enum OutputSpec {
+    /// (Re)build a `ConfigMap` of the same name as the `ZoneFile`.
+    #[default]
+    ConfigMap,
+
+    /// Sync records to a zone hosted on [Cloudflare](https://www.cloudflare.com/).
+    Cloudflare(CloudflareOutputSpec),
+}
+
+/// Configuration for pushing a rendered zone to Cloudflare's DNS API.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudflareOutputSpec {
+    /// Identifier of the Cloudflare zone records are synced into.
+    pub zone_id: String,
+
+    /// Account email associated with the API token.
+    pub email: String,
+
+    /// Reference to the `Secret` key holding the Cloudflare API token.
+    pub api_token_secret_ref: SecretRef,
+}
+
+/// Strategy for computing the next serial of a rebuilt zonefile.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum SerialPolicy {
+    /// Increment the previous serial by one on every rebuild.
+    #[default]
+    Increment,
+
+    /// Use an [RFC 1912 §2.2](https://datatracker.ietf.org/doc/html/rfc1912#section-2.2)
+    /// `YYYYMMDDnn` date-based serial, where `nn` starts at `00` and is bumped on
+    /// every rebuild that happens within the same day.
+    DateBased,
+}
+
+impl SerialPolicy {
+    /// Compute the next serial for a zone rebuilt under this policy.
+    ///
+    /// Regardless of policy, the result is guaranteed to be strictly greater than
+    /// `previous_serial`, falling back to a plain increment whenever the policy's
+    /// preferred candidate (e.g. a `dateBased` serial computed from a stale clock,
+    /// or one where `nn` has exhausted `99`) would not move the serial forward.
+    /// The one exception is a `previous_serial` of `u32::MAX`, where the increment
+    /// wraps around to `0`.
+    pub fn next_serial(&self, previous_serial: u32, today: chrono::NaiveDate) -> u32 {
+        let candidate = match self {
+            SerialPolicy::Increment => None,
+            SerialPolicy::DateBased => date_based_serial(today),
+        };
+
+        match candidate {
+            Some(candidate) if candidate > previous_serial => candidate,
+            _ => previous_serial.wrapping_add(1),
+        }
+    }
+}
+
+/// Compute the `YYYYMMDD00` base for an RFC 1912 date-based serial, returning
+/// `None` if the date doesn't fit a `u32` serial (i.e. any date from the year 4295 on).
+fn date_based_serial(date: chrono::NaiveDate) -> Option<u32> {
+    use chrono::Datelike;
+
+    let yyyymmdd = date.year() as u32 * 10000 + date.month() * 100 + date.day();
+    yyyymmdd.checked_mul(100)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn increment_policy_always_adds_one() {
+        assert_eq!(
+            SerialPolicy::Increment.next_serial(41, date(2026, 7, 27)),
+            42
+        );
+    }
+
+    #[test]
+    fn date_based_first_rebuild_of_the_day_uses_nn_00() {
+        let previous_serial = 2026072600; // yesterday's last serial
+        let today = date(2026, 7, 27);
+        assert_eq!(
+            SerialPolicy::DateBased.next_serial(previous_serial, today),
+            2026072700
+        );
+    }
+
+    #[test]
+    fn date_based_same_day_rebuild_increments_nn() {
+        let previous_serial = 2026072705; // nn = 05, already rebuilt 6 times today
+        let today = date(2026, 7, 27);
+        assert_eq!(
+            SerialPolicy::DateBased.next_serial(previous_serial, today),
+            2026072706
+        );
+    }
+
+    #[test]
+    fn date_based_day_rollover_resets_nn() {
+        let previous_serial = 2026072799; // last serial from the previous day
+        let today = date(2026, 7, 28);
+        assert_eq!(
+            SerialPolicy::DateBased.next_serial(previous_serial, today),
+            2026072800
+        );
+    }
+
+    #[test]
+    fn date_based_nn_exhausted_falls_back_to_increment() {
+        let previous_serial = 2026072799; // nn has hit 99 for today
+        let today = date(2026, 7, 27);
+        let next = SerialPolicy::DateBased.next_serial(previous_serial, today);
+        assert!(next > previous_serial);
+        assert_eq!(next, previous_serial + 1);
+    }
+
+    #[test]
+    fn date_based_clock_rollback_stays_monotonic() {
+        // Clock went backwards (or was wrong on a prior rebuild), leaving a
+        // previous serial from "the future" relative to `today`.
+        let previous_serial = 2026080100;
+        let today = date(2026, 7, 27);
+        let next = SerialPolicy::DateBased.next_serial(previous_serial, today);
+        assert!(next > previous_serial);
+        assert_eq!(next, previous_serial + 1);
+    }
+}
+
+/// Tunable parameters for a [`ZoneFile`]'s SOA record, mirroring the
+/// fields of [RFC 1035 §3.3.13](https://datatracker.ietf.org/doc/html/rfc1035#section-3.3.13),
+/// minus `serial`, which is managed by the controller.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct SoaSpec {
+    /// Primary nameserver for the zone.
+    pub mname: String,
+
+    /// Mailbox of the person responsible for the zone, in DNS dotted form
+    /// (e.g. `hostmaster.example.com.` for `hostmaster@example.com`).
+    pub rname: String,
+
+    /// Seconds before the zone should be refreshed by secondaries.
+    #[serde(default = "default_soa_refresh")]
+    pub refresh: u32,
+
+    /// Seconds before a failed refresh should be retried.
+    #[serde(default = "default_soa_retry")]
+    pub retry: u32,
+
+    /// Seconds after which the zone is no longer authoritative if refresh keeps failing.
+    #[serde(default = "default_soa_expire")]
+    pub expire: u32,
+
+    /// Negative caching TTL, in seconds.
+    #[serde(default = "default_soa_minimum")]
+    pub minimum: u32,
+
+    /// Default TTL applied to rendered records which don't specify their own.
+    #[serde(default = "default_soa_ttl")]
+    pub ttl: u32,
+}
+
+fn default_soa_refresh() -> u32 {
+    86400
+}
+
+fn default_soa_retry() -> u32 {
+    7200
+}
+
+fn default_soa_expire() -> u32 {
+    3600000
+}
+
+fn default_soa_minimum() -> u32 {
+    172800
+}
+
+fn default_soa_ttl() -> u32 {
+    86400
 }
 
 impl ZoneFile {
@@ -56,20 +316,69 @@ impl ZoneFile {
 
 /// Describes the current state of the [`ZoneFile`], tracks state of
 /// the upstream [`Zone`](kubizone_crds::Zone), to determine when the
-/// output `ConfigMap` should be re-generated.
+/// configured [`OutputSpec`] backend should be re-synced.
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ZoneFileStatus {
-    /// Last observed hash of the upstream [`Zone`](kubizone_crds::Zone)
+    /// Last observed hash of the upstream [`Zone`](kubizone_crds::Zone), keyed by zone
+    /// name (or [`COMBINED_OUTPUT_KEY`] when `spec.renderMode` is [`RenderMode::Combined`]).
     ///
-    /// Used by the zonefile controller to trigger configmap rebuilds
-    /// and zone serial rotation.
+    /// Used by the zonefile controller to trigger output rebuilds and zone serial rotation.
     pub hash: BTreeMap<String, String>,
 
     /// Serial of the latest generated zonefile.
     ///
-    /// The zonefile controller will automatically increment this value
-    /// whenever the zonefile configmap is rebuilt, in accordance with
-    /// [RFC 1912](https://datatracker.ietf.org/doc/html/rfc1912#section-2.2)
+    /// The zonefile controller automatically rotates this value whenever the
+    /// output is rebuilt, per `spec.serialPolicy`. The default [`SerialPolicy::Increment`]
+    /// is a plain `+1` and is not [RFC 1912 §2.2](https://datatracker.ietf.org/doc/html/rfc1912#section-2.2)
+    /// compliant; select [`SerialPolicy::DateBased`] for an RFC 1912 `YYYYMMDDnn` serial.
     pub serial: BTreeMap<String, u32>,
+
+    /// Result of the most recent sync to the configured [`OutputSpec`] backend, per zone.
+    #[serde(default)]
+    pub output: BTreeMap<String, OutputStatus>,
+
+    /// Conditions describing problems encountered while rendering the `ZoneFile`,
+    /// such as conflicting apex records or overlapping `$ORIGIN`s detected between
+    /// referenced zones in [`RenderMode::Combined`].
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+
+    /// Bounded per-zone history of past serial/hash rotations, newest last, trimmed
+    /// to `spec.historyDepth` entries.
+    ///
+    /// Gives operators an audit trail to correlate a bad output rebuild with the
+    /// upstream [`Zone`](kubizone_crds::Zone) change that triggered it.
+    #[serde(default)]
+    pub history: BTreeMap<String, Vec<HistoryEntry>>,
+}
+
+/// A single historical entry recorded whenever a [`ZoneFile`] zone's serial rotates.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    /// Serial prior to this rotation.
+    pub previous_serial: u32,
+
+    /// Hash of the upstream [`Zone`](kubizone_crds::Zone) prior to this rotation.
+    pub previous_hash: String,
+
+    /// When this rotation occurred.
+    ///
+    /// Uses [`k8s_openapi`]'s `Time` (rather than a bare `chrono::DateTime<Utc>`) so
+    /// (de)serialization and the `JsonSchema` derive ride on the same already-depended-on
+    /// `k8s_openapi`/`schemars` integration backing [`Condition`]'s `lastTransitionTime`,
+    /// instead of requiring their own separately-enabled `chrono` feature flags.
+    pub timestamp: Time,
+}
+
+/// Outcome of publishing a single zone's rendered zonefile to its configured backend.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputStatus {
+    /// Serial last successfully pushed to the backend.
+    pub serial: u32,
+
+    /// Human-readable result of the most recent sync attempt.
+    pub message: String,
 }